@@ -1,11 +1,29 @@
 pub const STMT_QUEUE_UP: &str = "INSERT INTO queue (`discord_id`, `name`, `note`, 
 `created`) VALUES (?,?,?,?)";
-pub const STMT_QUEUE_COUNT: &str = "SELECT COUNT(*) FROM queue";
 pub const STMT_QUEUE_ENTRY_EXIST: &str = "SELECT COUNT(discord_id) FROM queue WHERE discord_id = (?)";
 pub const STMT_UNQUEUE: &str = "DELETE FROM queue WHERE discord_id = (?)";
+pub const STMT_UPDATE_NOTE: &str = "UPDATE queue SET note = (?) WHERE discord_id = (?)";
 pub const STMT_QUEUE_NUMBER: &str = "SELECT COUNT(*) FROM queue WHERE created <= (SELECT created FROM queue WHERE discord_id = (?))";
 pub const STMT_LIST: &str =  "SELECT discord_id, name, note, created FROM queue ORDER BY created DESC";
 pub const STMT_REMOVE_ENTRY: &str = "DELETE FROM queue WHERE discord_id = (?)";
+pub const STMT_EXPIRE_SELECT: &str = "SELECT discord_id FROM queue WHERE created <= (?)";
+pub const STMT_EXPIRE_DELETE: &str = "DELETE FROM queue WHERE created <= (?)";
+pub const STMT_LIST_POSITIONS: &str = "SELECT discord_id, notified_pos, \
+(SELECT COUNT(*) FROM queue q2 WHERE q2.created <= q1.created) FROM queue q1";
+pub const STMT_UPDATE_NOTIFIED_POS: &str = "UPDATE queue SET notified_pos = (?) WHERE discord_id = (?)";
+pub const STMT_QUEUE_FETCH_NOTE: &str = "SELECT note FROM queue WHERE discord_id = (?)";
+pub const STMT_REMINDER_INSERT: &str = "INSERT INTO reminders (discord_id, trigger, note) VALUES (?,?,?)";
+pub const STMT_REMINDER_DUE_SELECT: &str = "SELECT rowid, discord_id, note FROM reminders WHERE trigger <= (?)";
+pub const STMT_REMINDER_DELETE: &str = "DELETE FROM reminders WHERE rowid = (?)";
+
+// Startup migrations, run via SqliteConnectionManager::with_init so a fresh
+// deploy (or one predating these columns/tables) provisions itself.
+pub const STMT_INIT_REMINDERS_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS reminders (discord_id TEXT, trigger INTEGER, note TEXT)";
+// queue.notified_pos has INTEGER affinity so the text we bind for it
+// (everything in this crate binds params via `.to_string()`) is coerced to
+// integer storage, matching the `Option<u32>` read in STMT_LIST_POSITIONS.
+pub const STMT_ADD_NOTIFIED_POS_COLUMN: &str = "ALTER TABLE queue ADD COLUMN notified_pos INTEGER";
 
 pub const MSG_ERROR: &str  =  "Sorry, there was a problem. Try DMing sensei.";
 pub const MSG_QUEUE_ALREADY: &str = "You have already queued";
@@ -15,5 +33,9 @@ pub const MSG_EMPTY_LIST: &str = "No one is looking for consultation";
 pub const MSG_MISSING_DISCORD_ID: &str = "Please provide a discord id";
 pub const MSG_INVALID_USER_ID: &str = "Id is not valid UserId";
 pub const MSG_DISCORD_ID_NOT_EXIST: &str = "discord_id does not exist";
+pub const MSG_EXPIRED_NOTICE: &str = "You have been removed from the consultation queue for inactivity. Feel free to queue up again!";
+pub const MSG_QUEUE_NEXT: &str = "You're next, sensei will see you now!";
+pub const MSG_MISSING_TIME_SPEC: &str = "Please provide when you'd like to be reminded, e.g. `2h30m` or `tomorrow 5pm`";
 
 pub const DISCORD_MSG_LIMIT: usize = 2000;
+pub const REMINDER_POLL_INTERVAL_SECS: u64 = 30;
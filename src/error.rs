@@ -0,0 +1,54 @@
+use std::fmt;
+
+// Unifies the error types this crate actually produces so command bodies can
+// use `?` instead of `.unwrap()`-ing every fallible DB/Discord call.
+#[derive(Debug)]
+pub enum BotError {
+    Db(rusqlite::Error),
+    Discord(serenity::Error),
+    Pool(r2d2::Error),
+    TaskJoin(String),
+    InvalidArgument,
+}
+
+impl fmt::Display for BotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BotError::Db(e) => write!(f, "database error: {}", e),
+            BotError::Discord(e) => write!(f, "discord error: {}", e),
+            BotError::Pool(e) => write!(f, "connection pool error: {}", e),
+            BotError::TaskJoin(e) => write!(f, "background task failed: {}", e),
+            BotError::InvalidArgument => write!(f, "invalid argument"),
+        }
+    }
+}
+
+impl std::error::Error for BotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BotError::Db(e) => Some(e),
+            BotError::Discord(e) => Some(e),
+            BotError::Pool(e) => Some(e),
+            BotError::TaskJoin(_) => None,
+            BotError::InvalidArgument => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for BotError {
+    fn from(e: rusqlite::Error) -> Self {
+        BotError::Db(e)
+    }
+}
+
+impl From<serenity::Error> for BotError {
+    fn from(e: serenity::Error) -> Self {
+        BotError::Discord(e)
+    }
+}
+
+impl From<r2d2::Error> for BotError {
+    fn from(e: r2d2::Error) -> Self {
+        BotError::Pool(e)
+    }
+}
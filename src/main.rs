@@ -1,4 +1,9 @@
+use chrono::Local;
+
+use r2d2_sqlite::SqliteConnectionManager;
+
 use rusqlite::Connection;
+use rusqlite::OptionalExtension;
 use rusqlite::NO_PARAMS;
 
 use serenity::async_trait;
@@ -10,6 +15,7 @@ use serenity::framework::standard::macros::group;
 use serenity::framework::standard::StandardFramework;
 use serenity::framework::standard::CommandResult;
 use serenity::framework::standard::Args;
+use serenity::http::Http;
 use serenity::model::channel::Message;
 use serenity::model::id::UserId;
 use serenity::model::gateway::Activity;
@@ -20,18 +26,26 @@ use serde::Deserialize;
 
 use std::fs::File;
 use std::io::BufReader;
+use std::sync::Arc;
+use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
-use tokio::sync::Mutex;
-
 mod constants;
 use crate::constants::*;
 
+mod error;
+use crate::error::BotError;
+
+mod time_parse;
+use crate::time_parse::resolve_trigger;
+
 // TypeMapKeys ////////////////////////////////////////////////////////////////////
+type DbPool = r2d2::Pool<SqliteConnectionManager>;
+
 struct Database;
 impl TypeMapKey for Database {
-    type Value = Mutex<Connection>;
+    type Value = DbPool;
 }
 
 struct OwnerId;
@@ -62,33 +76,200 @@ async fn say(ctx: &Context, msg: &Message, display: impl std::fmt::Display)  {
     }
 }
 
+// Splits `lines` into as many code-fenced messages as needed to stay under
+// DISCORD_MSG_LIMIT, never splitting a line across messages unless that line
+// alone exceeds the page budget, and appends a "(page n/m)" footer to each one.
+async fn say_split(ctx: &Context, msg: &Message, lines: &[String]) {
+    const FENCE: &str = "```";
+    const FOOTER_RESERVE: usize = 24; // room for "\n(page NNN/NNN)"
+
+    let budget = DISCORD_MSG_LIMIT - FENCE.len() * 2 - FOOTER_RESERVE;
+
+    let mut pages: Vec<String> = Vec::new();
+    let mut page = String::new();
+    for line in lines {
+        if !page.is_empty() && page.len() + line.len() + 1 > budget {
+            pages.push(page);
+            page = String::new();
+        }
 
-fn is_user_queued(discord_id: UserId, db: &Connection) -> bool {
-    let mut count: u32 = 0;
-    {
-        let mut stmt = db.prepare(STMT_QUEUE_ENTRY_EXIST).unwrap();
-        let mut rows = stmt.query(&[&discord_id.to_string()]).unwrap();
-        if let Some(row) = rows.next().unwrap() {
-            count = row.get(0).unwrap();
+        if line.len() > budget {
+            // A single line longer than the budget (e.g. a user-supplied
+            // `note`) can't share a page with anything else; hard-split it
+            // so it never produces a message over DISCORD_MSG_LIMIT.
+            let mut rest = line.as_str();
+            while rest.len() > budget {
+                let split_at = floor_char_boundary(rest, budget);
+                pages.push(rest[..split_at].to_string());
+                rest = &rest[split_at..];
+            }
+            page.push_str(rest);
+            page.push('\n');
+            continue;
+        }
+
+        page.push_str(line.as_str());
+        page.push('\n');
+    }
+    if !page.is_empty() {
+        pages.push(page);
+    }
+
+    let total = pages.len();
+    for (i, page) in pages.into_iter().enumerate() {
+        let reply = format!("{}{}{}\n(page {}/{})", FENCE, page, FENCE, i + 1, total);
+        say(ctx, msg, reply).await;
+    }
+}
+
+// Returns the largest byte index <= `max` that lands on a UTF-8 char
+// boundary of `s`, so hard-splitting a line never panics on a multi-byte
+// character straddling the cut point.
+fn floor_char_boundary(s: &str, max: usize) -> usize {
+    let mut idx = max.min(s.len());
+    while idx > 0 && !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+
+// Checks out a connection from `pool` and runs `f` on a blocking-safe thread
+// so a slow query never stalls the async runtime. Pool exhaustion and a
+// panicking blocking task are reported as `BotError` instead of propagating
+// a panic. Shared by `with_db` (command handlers) and the periodic
+// background tasks below, which hold their own clone of the pool.
+async fn run_blocking_db<F, T>(pool: DbPool, f: F) -> Result<T, BotError>
+where
+    F: FnOnce(&Connection) -> Result<T, BotError> + Send + 'static,
+    T: Send + 'static,
+{
+    match tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        f(&conn)
+    }).await {
+        Ok(result) => result,
+        Err(e) => Err(BotError::TaskJoin(e.to_string())),
+    }
+}
+
+// Resolves the shared pool from `ctx` and runs `f` via `run_blocking_db`.
+async fn with_db<F, T>(ctx: &Context, f: F) -> Result<T, BotError>
+where
+    F: FnOnce(&Connection) -> Result<T, BotError> + Send + 'static,
+    T: Send + 'static,
+{
+    let pool = {
+        let data = ctx.data.read().await;
+        data.get::<Database>().unwrap().clone()
+    };
+
+    run_blocking_db(pool, f).await
+}
+
+// Unwraps a command's `Result`, sending MSG_ERROR and logging the cause on
+// failure. Returns `None` on error so callers can `return Ok(())` early.
+async fn handle_result<T>(ctx: &Context, msg: &Message, result: Result<T, BotError>) -> Option<T> {
+    match result {
+        Ok(v) => Some(v),
+        Err(e) => {
+            println!("Error handling command: {}", e);
+            say(ctx, msg, MSG_ERROR).await;
+            None
         }
     }
+}
+
+fn is_user_queued(discord_id: UserId, db: &Connection) -> Result<bool, BotError> {
+    let mut stmt = db.prepare(STMT_QUEUE_ENTRY_EXIST)?;
+    let mut rows = stmt.query(&[&discord_id.to_string()])?;
+
+    let mut count: u32 = 0;
+    if let Some(row) = rows.next()? {
+        count = row.get(0)?;
+    }
+
+    return Ok(count > 0);
+}
+
+// Recomputes every remaining user's queue position and DMs whoever moved up
+// since the last time we notified them, using `notified_pos` to keep the
+// notification idempotent across repeated calls.
+async fn notify_position_changes(ctx: &Context) {
+    // Read and persist notified_pos in the same connection checkout so an
+    // ordering change with N queued users costs one checkout, not N.
+    let result: Result<Vec<(String, u32, bool)>, BotError> = with_db(ctx, |db| {
+        let mut stmt = db.prepare(STMT_LIST_POSITIONS)?;
+        let mut query_rows = stmt.query(NO_PARAMS)?;
+        let mut rows: Vec<(String, Option<u32>, u32)> = Vec::new();
+        while let Some(row) = query_rows.next()? {
+            rows.push((row.get(0)?, row.get(1)?, row.get(2)?));
+        }
+
+        let mut notices = Vec::with_capacity(rows.len());
+        for (discord_id, notified_pos, pos) in rows {
+            let moved_up = match notified_pos {
+                Some(prev) => pos < prev,
+                None => true,
+            };
+            db.execute(STMT_UPDATE_NOTIFIED_POS, &[&pos.to_string(), &discord_id])?;
+            notices.push((discord_id, pos, moved_up));
+        }
 
-    return count > 0;
+        Ok(notices)
+    }).await;
+
+    let notices = match result {
+        Ok(notices) => notices,
+        Err(e) => {
+            println!("Error recomputing queue positions: {}", e);
+            return;
+        }
+    };
+
+    for (discord_id, pos, moved_up) in notices {
+        if !moved_up {
+            continue;
+        }
+
+        let user_id = match discord_id.parse::<u64>() {
+            Ok(id) => UserId(id),
+            Err(_) => {
+                println!("Error parsing discord_id {:?} as a UserId", discord_id);
+                continue;
+            }
+        };
+        let notice = if pos == 1 {
+            MSG_QUEUE_NEXT.to_string()
+        } else {
+            format!("Your queue position is now **{}**", pos)
+        };
+
+        match user_id.create_dm_channel(&ctx.http).await {
+            Ok(channel) => {
+                if let Err(why) = channel.say(&ctx.http, notice).await {
+                    println!("Error sending position DM: {:?}", why);
+                }
+            }
+            Err(why) => println!("Error opening DM channel: {:?}", why),
+        }
+    }
 }
 
-fn args_to_string(mut args: Args) -> String {
+fn args_to_string(mut args: Args) -> Result<String, BotError> {
     let mut ret = String::with_capacity(128);
-    ret.push_str(args.single::<String>().unwrap().as_str());
+    ret.push_str(args.single::<String>().map_err(|_| BotError::InvalidArgument)?.as_str());
     for arg in args.iter::<String>() {
-        ret.push_str(format!(" {}", arg.unwrap()).as_str());
+        let arg = arg.map_err(|_| BotError::InvalidArgument)?;
+        ret.push_str(format!(" {}", arg).as_str());
     }
 
-    return ret;
+    return Ok(ret);
 }
 
 // Commands ///////////////////////////////
 #[group]
-#[commands(version, help, queue, unqueue, when, note)]
+#[commands(version, help, queue, unqueue, when, note, remindme)]
 struct General;
 
 #[group]
@@ -109,73 +290,190 @@ async fn help(ctx: &Context, msg: &Message) -> CommandResult {
 }
 
 #[command]
-async fn note(ctx: &Context, msg: &Message) -> CommandResult {
-    say(ctx, msg, "Not implemented").await;
+async fn note(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let mut note = String::from("");
+    if args.len() > 0 {
+        note = match handle_result(ctx, msg, args_to_string(args)).await {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+    }
+
+    let author_id = msg.author.id;
+    let result: Result<bool, BotError> = with_db(ctx, move |db| {
+        if !is_user_queued(author_id, db)? {
+            return Ok(false);
+        }
+        db.execute(STMT_UPDATE_NOTE, &[&note, &author_id.to_string()])?;
+        Ok(true)
+    }).await;
+
+    let updated = match handle_result(ctx, msg, result).await {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    if updated {
+        say(ctx, msg, "Note updated!").await;
+    } else {
+        say(ctx, msg, MSG_NOT_IN_QUEUE).await;
+    }
     return Ok(());
 }
 
 #[command]
-async fn queue(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
-    // Check if discord id exists
-    let data = ctx.data.read().await;
-    let db = data.get::<Database>().unwrap().lock().await;
-  
-    if !is_user_queued(msg.author.id, &db) {
-        say(ctx, msg, MSG_QUEUE_ALREADY).await;
+async fn remindme(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    if args.len() == 0 {
+        say(ctx, msg, MSG_MISSING_TIME_SPEC).await;
         return Ok(());
     }
 
+    let time_spec = match handle_result(ctx, msg, args_to_string(args)).await {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    let now = Local::now();
+    let trigger = match resolve_trigger(&time_spec, now) {
+        Ok(t) => t,
+        Err(e) => {
+            say(ctx, msg, e.to_string()).await;
+            return Ok(());
+        }
+    };
+    let trigger_millis = trigger.timestamp_millis();
+
+    let author_id = msg.author.id;
+    let result: Result<bool, BotError> = with_db(ctx, move |db| {
+        let note: Option<String> = db.query_row(
+            STMT_QUEUE_FETCH_NOTE,
+            &[&author_id.to_string()],
+            |row| row.get(0),
+        ).optional()?;
+
+        let note = match note {
+            Some(n) => n,
+            None => return Ok(false),
+        };
+
+        db.execute(STMT_REMINDER_INSERT, &[&author_id.to_string(), &trigger_millis.to_string(), &note])?;
+        Ok(true)
+    }).await;
+
+    let queued = match handle_result(ctx, msg, result).await {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    if queued {
+        say(ctx, msg, format!("Got it! I'll remind you at **{}**", trigger.format("%Y-%m-%d %H:%M"))).await;
+    } else {
+        say(ctx, msg, MSG_NOT_IN_QUEUE).await;
+    }
+
+    return Ok(());
+}
+
+enum QueueOutcome {
+    AlreadyQueued,
+    Error,
+    Queued(u32),
+}
+
+#[command]
+async fn queue(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let mut note = String::from("");
     if args.len() > 0 {
-        note = args_to_string(args);
+        note = match handle_result(ctx, msg, args_to_string(args)).await {
+            Some(v) => v,
+            None => return Ok(()),
+        };
     }
 
-    // Insert into the database
-    {
+    let author_id = msg.author.id;
+    let author_name = msg.author.name.clone();
+
+    let result: Result<QueueOutcome, BotError> = with_db(ctx, move |db| {
+        // Check if discord id already exists
+        if is_user_queued(author_id, db)? {
+            return Ok(QueueOutcome::AlreadyQueued);
+        }
+
+        // Insert into the database
         let since_the_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-    
-        let rows_affected = db.execute(STMT_QUEUE_UP, 
+        let rows_affected = db.execute(STMT_QUEUE_UP,
                                        &[
-                                          &msg.author.id.to_string(),
-                                          &msg.author.name,
+                                          &author_id.to_string(),
+                                          &author_name,
                                           &note,
-                                          &since_the_epoch.as_millis().to_string(), 
-                                       ]).unwrap();
+                                          &since_the_epoch.as_millis().to_string(),
+                                       ])?;
         if rows_affected == 0 {
-            say(ctx, msg, MSG_ERROR).await;
-            return Ok(());
+            return Ok(QueueOutcome::Error);
         }
-    }
 
-    //Get the queue number
-    {
-        let mut queue_length: u32 = 0;
+        // Get this row's own queue position (by `created`, same formula as
+        // STMT_LIST_POSITIONS). We use this instead of a plain `COUNT(*) FROM queue`
+        // total because another connection could insert between our INSERT
+        // and here, and a plain total would then count a later joiner who
+        // shouldn't affect our reported/seeded position.
+        let mut own_position: u32 = 0;
         {
-            let mut stmt = db.prepare(STMT_QUEUE_COUNT).unwrap();
-            let mut rows = stmt.query(NO_PARAMS).unwrap();
-            if let Some(row) = rows.next().unwrap() {
-                queue_length = row.get(0).unwrap();    
+            let mut stmt = db.prepare(STMT_QUEUE_NUMBER)?;
+            let mut rows = stmt.query(&[&author_id.to_string()])?;
+            if let Some(row) = rows.next()? {
+                own_position = row.get(0)?;
             }
         }
-        say(ctx, msg, format!("Queued! You are **{}** on the queue", queue_length)).await;
+
+        // Seed notified_pos to our own position so notify_position_changes
+        // doesn't treat this join itself as a move up and send a redundant
+        // DM right after this reply.
+        db.execute(STMT_UPDATE_NOTIFIED_POS, &[&own_position.to_string(), &author_id.to_string()])?;
+
+        Ok(QueueOutcome::Queued(own_position))
+    }).await;
+
+    let outcome = match handle_result(ctx, msg, result).await {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    match outcome {
+        QueueOutcome::AlreadyQueued => {
+            say(ctx, msg, MSG_QUEUE_ALREADY).await;
+            return Ok(());
+        }
+        QueueOutcome::Error => {
+            say(ctx, msg, MSG_ERROR).await;
+            return Ok(());
+        }
+        QueueOutcome::Queued(position) => {
+            say(ctx, msg, format!("Queued! You are **{}** on the queue", position)).await;
+        }
     }
 
+    notify_position_changes(ctx).await;
+
     return Ok(());
 }
 
 
 #[command]
 async fn unqueue(ctx: &Context, msg: &Message) -> CommandResult {
-    let data = ctx.data.read().await;
-    let db = data.get::<Database>().unwrap().lock().await;
+    let author_id = msg.author.id;
+    let result: Result<usize, BotError> = with_db(ctx, move |db| {
+        Ok(db.execute(STMT_UNQUEUE, &[&author_id.to_string()])?)
+    }).await;
 
-    let rows_affected = db.execute(STMT_UNQUEUE, 
-                                   &[
-                                       &msg.author.id.to_string()
-                                   ]).unwrap();
+    let rows_affected = match handle_result(ctx, msg, result).await {
+        Some(v) => v,
+        None => return Ok(()),
+    };
 
     if rows_affected > 0 {
         say(ctx, msg, MSG_REMOVE_QUEUE_SUCCESS).await;
+        notify_position_changes(ctx).await;
     } else {
         say(ctx, msg, MSG_NOT_IN_QUEUE).await;
     }
@@ -184,29 +482,34 @@ async fn unqueue(ctx: &Context, msg: &Message) -> CommandResult {
 
 #[command]
 async fn when(ctx: &Context, msg: &Message) -> CommandResult {
-    let data = ctx.data.read().await;
-    let db = data.get::<Database>().unwrap().lock().await;
+    let author_id = msg.author.id;
+    let result: Result<Option<Option<u32>>, BotError> = with_db(ctx, move |db| {
+        if !is_user_queued(author_id, db)? {
+            return Ok(None);
+        }
 
-    if !is_user_queued(msg.author.id, &db) {
-        say(ctx, msg, MSG_NOT_IN_QUEUE).await;
-        return Ok(());
-    }
+        let mut queue_number_opt: Option<u32> = None;
+        {
+            let mut stmt = db.prepare(STMT_QUEUE_NUMBER)?;
+            let mut rows = stmt.query(&[&author_id.to_string()])?;
+            if let Some(row) = rows.next()? {
+                queue_number_opt = Some(row.get(0)?);
+            }
+        }
 
-    let mut queue_number_opt: Option<u32> = None;                           
-    {                                                                       
-        let mut stmt = db.prepare(STMT_QUEUE_NUMBER).unwrap();                          
-        let mut rows = stmt.query(&[&msg.author.id.to_string()]).unwrap();  
-        if let Some(row) = rows.next().unwrap() {                           
-            queue_number_opt = Some(row.get(0).unwrap());                   
-        }                                                                   
-    }                                                                       
-
-    if let Some(queue_number) = queue_number_opt {    
-        say(ctx, msg, format!("Your queue number is: **{}**", queue_number)).await;
-    } else {
-        say(ctx, msg, MSG_ERROR).await;
-    }
+        Ok(Some(queue_number_opt))
+    }).await;
 
+    let outcome = match handle_result(ctx, msg, result).await {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    match outcome {
+        None => say(ctx, msg, MSG_NOT_IN_QUEUE).await,
+        Some(None) => say(ctx, msg, MSG_ERROR).await,
+        Some(Some(queue_number)) => say(ctx, msg, format!("Your queue number is: **{}**", queue_number)).await,
+    }
 
     return Ok(());
 }
@@ -225,54 +528,40 @@ async fn list(ctx: &Context, msg: &Message) -> CommandResult {
     if !is_owner(&ctx, &msg).await {
         return Ok(());
     }
-    let data = ctx.data.read().await;
-    let db = data.get::<Database>().unwrap().lock().await;
-    let mut entries: Vec<QueueEntry> = Vec::new(); 
-    {
+
+    let result: Result<Vec<QueueEntry>, BotError> = with_db(ctx, |db| {
         let mut stmt = db.prepare(STMT_LIST)?;
-        let rows = stmt.query_map(NO_PARAMS , |row| {
+        let rows = stmt.query_map(NO_PARAMS, |row| {
             Ok(QueueEntry {
-                discord_id: row.get(0).unwrap(),
-                name: row.get(1).unwrap(),
-                note: row.get(2).unwrap(),
-                created: row.get(3).unwrap(),
+                discord_id: row.get(0)?,
+                name: row.get(1)?,
+                note: row.get(2)?,
+                created: row.get(3)?,
             })
         })?;
+
+        let mut entries = Vec::new();
         for row in rows {
             entries.push(row?);
         }
-    }
-   
+        Ok(entries)
+    }).await;
+
+    let entries = match handle_result(ctx, msg, result).await {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
     if entries.len() == 0 {
         say(ctx, msg, MSG_EMPTY_LIST).await;
         return Ok(());
     }
 
-    let mut reply: String = String::from("```");
-    {
-        let mut buffer: String = String::new();
-        for entry in entries {
-            buffer.push_str(entry.discord_id.as_str());
-            buffer.push('\t');
-            buffer.push_str(entry.created.as_str());
-            buffer.push('\t');
-            buffer.push_str(entry.name.as_str());
-            buffer.push('\t');
-            buffer.push_str(entry.note.as_str());
-            buffer.push('\n');
-
-            if reply.len() + buffer.len() < DISCORD_MSG_LIMIT {
-                reply.push_str(buffer.as_str());
-                buffer.clear();
-                println!("{}", buffer);
-            } else {
-                break;
-            }
-        }
-    }    
+    let lines: Vec<String> = entries.iter().map(|entry| {
+        format!("{}\t{}\t{}\t{}", entry.discord_id, entry.created, entry.name, entry.note)
+    }).collect();
 
-    reply.push_str("```");
-    say(ctx, msg, reply).await;
+    say_split(ctx, msg, &lines).await;
 
     return Ok(());
 }
@@ -297,18 +586,23 @@ async fn remove(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
     }
  
     let discord_id_str = discord_id.as_u64().to_string();
-    let data = ctx.data.read().await;
-    let db = data.get::<Database>().unwrap().lock().await;
+    let discord_id_for_db = discord_id_str.clone();
+    let result: Result<usize, BotError> = with_db(ctx, move |db| {
+        Ok(db.execute(STMT_REMOVE_ENTRY, &[&discord_id_for_db])?)
+    }).await;
+
+    let rows_affected = match handle_result(ctx, msg, result).await {
+        Some(v) => v,
+        None => return Ok(()),
+    };
 
-    let rows_affected = db.execute(STMT_REMOVE_ENTRY, &[&discord_id_str]).unwrap();
     if rows_affected == 0 {
-        say(ctx, msg, MSG_DISCORD_ID_NOT_EXIST).await; 
+        say(ctx, msg, MSG_DISCORD_ID_NOT_EXIST).await;
         return Ok(());
     }
 
     say(ctx, msg, format!("Removed {}", discord_id_str)).await;
-
-
+    notify_position_changes(ctx).await;
 
     return Ok(());
 }
@@ -320,6 +614,7 @@ struct Config {
     prefix: String,
     db_path: String,
     owner_id: u64,
+    queue_ttl_secs: u64,
 }
 
 struct Handler; 
@@ -333,10 +628,129 @@ struct Handler;
 async fn is_owner(ctx: &Context, msg: &Message) -> bool {
     let data = ctx.data.read().await;
     let owner_id = data.get::<OwnerId>().unwrap();
-    
+
     return msg.author.id == *owner_id;
 }
 
+// Periodically drops queue entries older than `queue_ttl_secs`, DMing each
+// affected user so they know why they disappeared from the queue.
+fn spawn_expiry_task(pool: DbPool, http: Arc<Http>, queue_ttl_secs: u64) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(queue_ttl_secs));
+        loop {
+            ticker.tick().await;
+
+            let result: Result<Vec<String>, BotError> = run_blocking_db(pool.clone(), move |conn| {
+                let since_the_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+                let threshold = since_the_epoch.as_millis().saturating_sub((queue_ttl_secs as u128) * 1000).to_string();
+
+                let mut expired: Vec<String> = Vec::new();
+                {
+                    let mut stmt = conn.prepare(STMT_EXPIRE_SELECT)?;
+                    let mut rows = stmt.query(&[&threshold])?;
+                    while let Some(row) = rows.next()? {
+                        expired.push(row.get(0)?);
+                    }
+                }
+
+                if !expired.is_empty() {
+                    conn.execute(STMT_EXPIRE_DELETE, &[&threshold])?;
+                }
+
+                Ok(expired)
+            }).await;
+
+            let expired = match result {
+                Ok(expired) => expired,
+                Err(e) => {
+                    println!("Error expiring stale queue entries: {}", e);
+                    continue;
+                }
+            };
+
+            for discord_id in expired {
+                let user_id = match discord_id.parse::<u64>() {
+                    Ok(id) => UserId(id),
+                    Err(_) => {
+                        println!("Error parsing discord_id {:?} as a UserId", discord_id);
+                        continue;
+                    }
+                };
+                match user_id.create_dm_channel(&http).await {
+                    Ok(channel) => {
+                        if let Err(why) = channel.say(&http, MSG_EXPIRED_NOTICE).await {
+                            println!("Error sending expiry DM: {:?}", why);
+                        }
+                    }
+                    Err(why) => println!("Error opening DM channel: {:?}", why),
+                }
+            }
+        }
+    });
+}
+
+// Polls for due reminders, deletes the due rows, then DMs each affected user,
+// so a reminder fires at most once; a reminder whose DM fails is not resent.
+fn spawn_reminder_task(pool: DbPool, http: Arc<Http>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(REMINDER_POLL_INTERVAL_SECS));
+        loop {
+            ticker.tick().await;
+
+            let result: Result<Vec<(i64, String, String)>, BotError> = run_blocking_db(pool.clone(), |conn| {
+                let now_millis = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis().to_string();
+
+                let mut due: Vec<(i64, String, String)> = Vec::new();
+                {
+                    let mut stmt = conn.prepare(STMT_REMINDER_DUE_SELECT)?;
+                    let mut rows = stmt.query(&[&now_millis])?;
+                    while let Some(row) = rows.next()? {
+                        due.push((row.get(0)?, row.get(1)?, row.get(2)?));
+                    }
+                }
+
+                for (rowid, _, _) in &due {
+                    conn.execute(STMT_REMINDER_DELETE, &[rowid])?;
+                }
+
+                Ok(due)
+            }).await;
+
+            let due = match result {
+                Ok(due) => due,
+                Err(e) => {
+                    println!("Error checking reminders: {}", e);
+                    continue;
+                }
+            };
+
+            for (_, discord_id, note) in due {
+                let user_id = match discord_id.parse::<u64>() {
+                    Ok(id) => UserId(id),
+                    Err(_) => {
+                        println!("Error parsing discord_id {:?} as a UserId", discord_id);
+                        continue;
+                    }
+                };
+                let message = if note.is_empty() {
+                    "Reminder: it's time for your consult!".to_string()
+                } else {
+                    format!("Reminder: it's time for your consult! Your note: {}", note)
+                };
+
+                match user_id.create_dm_channel(&http).await {
+                    Ok(channel) => {
+                        if let Err(why) = channel.say(&http, message).await {
+                            println!("Error sending reminder DM: {:?}", why);
+                        }
+                    }
+                    Err(why) => println!("Error opening DM channel: {:?}", why),
+                }
+            }
+        }
+    });
+}
+
 #[tokio::main]
 async fn main() {
     let mut client: Client;
@@ -363,14 +777,41 @@ async fn main() {
             .await
             .unwrap();
 
-        let mut data = client.data.write().await;
         // database
-        {
-            let conn = Connection::open(config.db_path).unwrap();
-            data.insert::<Database>(Mutex::new(conn));
-            data.insert::<OwnerId>(UserId(config.owner_id));        
+        let manager = SqliteConnectionManager::file(&config.db_path)
+            .with_init(|conn| {
+                // with_init's migrations are schema-modifying writes that can
+                // run from several pooled connections close together (e.g.
+                // spawn_expiry_task and spawn_reminder_task both fire their
+                // first tick at startup); without a busy timeout a lock
+                // collision fails immediately instead of waiting it out.
+                conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000;")?;
+                conn.execute_batch(STMT_INIT_REMINDERS_TABLE)?;
+
+                // SQLite has no "ADD COLUMN IF NOT EXISTS", and with_init
+                // runs once per pooled connection, so swallow the expected
+                // "duplicate column name" error on every run after the
+                // first. The `queue` table itself is provisioned outside
+                // this crate, so also tolerate it not existing yet.
+                if let Err(e) = conn.execute(STMT_ADD_NOTIFIED_POS_COLUMN, NO_PARAMS) {
+                    let msg = e.to_string();
+                    if !msg.contains("duplicate column name") && !msg.contains("no such table") {
+                        return Err(e);
+                    }
+                }
+
+                Ok(())
+            });
+        let pool = r2d2::Pool::new(manager).unwrap();
 
+        {
+            let mut data = client.data.write().await;
+            data.insert::<Database>(pool.clone());
+            data.insert::<OwnerId>(UserId(config.owner_id));
         }
+
+        spawn_expiry_task(pool.clone(), client.cache_and_http.http.clone(), config.queue_ttl_secs);
+        spawn_reminder_task(pool, client.cache_and_http.http.clone());
     }
 
     if let Err(why) = client.start().await {
@@ -0,0 +1,221 @@
+use std::fmt;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Duration as ChronoDuration;
+use chrono::Local;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+
+#[derive(Debug)]
+pub enum TimeParseError {
+    ZeroOrPast,
+    Unparseable,
+}
+
+impl fmt::Display for TimeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TimeParseError::ZeroOrPast => write!(f, "That time is already in the past, sensei can't help you travel back in time!"),
+            TimeParseError::Unparseable => write!(f, "Sorry, I couldn't understand that time. Try something like `2h30m` or `tomorrow 5pm`"),
+        }
+    }
+}
+
+enum RelativeParse {
+    NoMatch,
+    Matched(Duration),
+    Invalid,
+}
+
+// Scans `input` for `<number><unit>` tokens (unit in {s,m,h,d,w}, plus the
+// usual "min"/"hour"/etc. spellings) and sums them. Returns `NoMatch` when
+// `input` doesn't even start with a digit, or when no `<number><unit>` token
+// has matched yet (so the caller can fall back to absolute-time parsing,
+// e.g. "14:30" or "2026-07-28 14:30" both start with digits but aren't
+// durations); returns `Invalid` once at least one token has matched but a
+// later one is malformed, so the caller knows NOT to fall back (the user
+// meant a duration and got it wrong).
+fn parse_relative(input: &str) -> RelativeParse {
+    let trimmed = input.trim();
+    if trimmed.is_empty() || !trimmed.chars().next().unwrap().is_ascii_digit() {
+        return RelativeParse::NoMatch;
+    }
+
+    let mut total = Duration::new(0, 0);
+    let mut rest = trimmed;
+    let mut matched_any = false;
+
+    while !rest.is_empty() {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+
+        let fail = |matched_any: bool| if matched_any { RelativeParse::Invalid } else { RelativeParse::NoMatch };
+
+        let digits_end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits_end == 0 {
+            return fail(matched_any);
+        }
+        let number: u64 = match rest[..digits_end].parse() {
+            Ok(n) => n,
+            Err(_) => return fail(matched_any),
+        };
+        rest = &rest[digits_end..];
+
+        let unit_end = rest.find(|c: char| c.is_ascii_digit() || c.is_whitespace()).unwrap_or(rest.len());
+        if unit_end == 0 {
+            return fail(matched_any);
+        }
+        let unit = &rest[..unit_end];
+        rest = &rest[unit_end..];
+
+        let secs_per_unit: u64 = match unit {
+            "s" | "sec" | "secs" | "second" | "seconds" => 1,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3600,
+            "d" | "day" | "days" => 86400,
+            "w" | "week" | "weeks" => 604800,
+            _ => return fail(matched_any),
+        };
+
+        total = total.saturating_add(Duration::from_secs(number.saturating_mul(secs_per_unit)));
+        matched_any = true;
+    }
+
+    RelativeParse::Matched(total)
+}
+
+fn parse_clock_ampm(input: &str) -> Option<NaiveTime> {
+    let input = input.trim();
+    NaiveTime::parse_from_str(input, "%I:%M%p")
+        .or_else(|_| NaiveTime::parse_from_str(input, "%I:%M %p"))
+        .ok()
+        .or_else(|| {
+            // chrono needs minutes to build a NaiveTime, so give an hour-only
+            // "5pm" / "5 pm" an explicit ":00".
+            let split = input.find(|c: char| !c.is_ascii_digit())?;
+            let (hour, ampm) = input.split_at(split);
+            NaiveTime::parse_from_str(&format!("{}:00 {}", hour, ampm.trim()), "%I:%M %p").ok()
+        })
+}
+
+// Tries "YYYY-MM-DD HH:MM", "tomorrow HH(am|pm)" and "HH:MM", resolved
+// against `now`. A bare "HH:MM" rolls to the next day when that clock time
+// has already passed today.
+fn parse_absolute(input: &str, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    let trimmed = input.trim();
+
+    if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M") {
+        return Local.from_local_datetime(&naive).single();
+    }
+
+    if let Some(rest) = trimmed.strip_prefix("tomorrow") {
+        let time = parse_clock_ampm(rest)?;
+        let date = (now + ChronoDuration::days(1)).date_naive();
+        return Local.from_local_datetime(&date.and_time(time)).single();
+    }
+
+    if let Ok(time) = NaiveTime::parse_from_str(trimmed, "%H:%M") {
+        let today = now.date_naive();
+        let mut candidate = Local.from_local_datetime(&today.and_time(time)).single()?;
+        if candidate <= now {
+            candidate = Local.from_local_datetime(&(today + ChronoDuration::days(1)).and_time(time)).single()?;
+        }
+        return Some(candidate);
+    }
+
+    None
+}
+
+// Resolves a user-supplied `remindme` argument (relative duration or
+// absolute time) against `now`, rejecting anything that lands in the past.
+pub fn resolve_trigger(input: &str, now: DateTime<Local>) -> Result<DateTime<Local>, TimeParseError> {
+    match parse_relative(input) {
+        RelativeParse::Matched(duration) => {
+            if duration.as_secs() == 0 {
+                return Err(TimeParseError::ZeroOrPast);
+            }
+            // Cap at a century so an absurdly large duration saturates
+            // instead of overflowing chrono's DateTime arithmetic.
+            let capped_secs = duration.as_secs().min(100 * 365 * 24 * 3600);
+            let offset = ChronoDuration::seconds(capped_secs as i64);
+            return now.checked_add_signed(offset).ok_or(TimeParseError::Unparseable);
+        }
+        RelativeParse::Invalid => return Err(TimeParseError::Unparseable),
+        RelativeParse::NoMatch => {}
+    }
+
+    match parse_absolute(input, now) {
+        Some(trigger) if trigger > now => Ok(trigger),
+        Some(_) => Err(TimeParseError::ZeroOrPast),
+        None => Err(TimeParseError::Unparseable),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn fixed_now() -> DateTime<Local> {
+        let naive = NaiveDate::from_ymd_opt(2026, 7, 26).unwrap().and_hms_opt(10, 0, 0).unwrap();
+        Local.from_local_datetime(&naive).single().unwrap()
+    }
+
+    #[test]
+    fn relative_duration() {
+        let now = fixed_now();
+        let trigger = resolve_trigger("2h30m", now).unwrap();
+        assert_eq!(trigger, now + ChronoDuration::minutes(150));
+    }
+
+    #[test]
+    fn relative_zero_is_rejected() {
+        let now = fixed_now();
+        assert!(matches!(resolve_trigger("0s", now), Err(TimeParseError::ZeroOrPast)));
+    }
+
+    #[test]
+    fn relative_overflow_is_capped_not_panicking() {
+        let now = fixed_now();
+        assert!(resolve_trigger("99999999999999w", now).is_ok());
+    }
+
+    #[test]
+    fn bare_clock_time_later_today() {
+        let now = fixed_now();
+        let trigger = resolve_trigger("14:30", now).unwrap();
+        assert_eq!(trigger.date_naive(), now.date_naive());
+        assert_eq!(trigger.format("%H:%M").to_string(), "14:30");
+    }
+
+    #[test]
+    fn bare_clock_time_rolls_to_tomorrow() {
+        let now = fixed_now();
+        let trigger = resolve_trigger("09:00", now).unwrap();
+        assert_eq!(trigger.date_naive(), (now + ChronoDuration::days(1)).date_naive());
+    }
+
+    #[test]
+    fn absolute_datetime() {
+        let now = fixed_now();
+        let trigger = resolve_trigger("2026-07-28 14:30", now).unwrap();
+        assert_eq!(trigger.format("%Y-%m-%d %H:%M").to_string(), "2026-07-28 14:30");
+    }
+
+    #[test]
+    fn tomorrow_with_ampm() {
+        let now = fixed_now();
+        let trigger = resolve_trigger("tomorrow 5pm", now).unwrap();
+        assert_eq!(trigger.date_naive(), (now + ChronoDuration::days(1)).date_naive());
+        assert_eq!(trigger.format("%H:%M").to_string(), "17:00");
+    }
+
+    #[test]
+    fn unparseable_garbage() {
+        let now = fixed_now();
+        assert!(matches!(resolve_trigger("blahblah", now), Err(TimeParseError::Unparseable)));
+    }
+}